@@ -29,18 +29,59 @@ const THRESHOLD_PREFIX: u8 = 0x08;
 /// encode_key(2, WireType::LengthDelimited, &mut buf);
 /// ```
 const PUBKEY_PREFIX: u8 = 0x12;
+/// This is the result of (similar to above)
+/// ```ignore
+/// encode_key(3, WireType::Varint, &mut buf);
+/// ```
+/// Not part of the upstream amino spec - weighted multisig threshold keys
+/// are this fork's own extension, and a per-signer weight is only emitted
+/// when it carries real information (see `amino_bytes`).
+const WEIGHT_PREFIX: u8 = 0x18;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct MultisigThresholdPubKey {
     threshold: u32,
     public_keys: Vec<CosmosPubKey>,
+    // One weight per entry in `public_keys`, same index. Defaults to 1 for
+    // every key when constructed via `new`, which keeps plain (unweighted)
+    // threshold keys behaving exactly as before.
+    weights: Vec<u64>,
 }
 
 impl MultisigThresholdPubKey {
     pub fn new(threshold: u32, public_keys: Vec<CosmosPubKey>) -> Self {
+        let weights = vec![1u64; public_keys.len()];
+        Self::new_weighted(threshold, public_keys, weights)
+    }
+
+    /// Like `new`, but lets each signer carry its own weight instead of a
+    /// flat vote of 1. `verify_bytes` then passes once the summed weight of
+    /// the signers who produced a valid signature meets `threshold`.
+    ///
+    /// `weights` is resized to match `public_keys.len()` if the caller got
+    /// it wrong: this must not panic in release builds, since
+    /// `verify_bytes_indexed` indexes `weights` by signer position and an
+    /// under-length vector would otherwise panic during verification rather
+    /// than at construction. Missing weights default to 0 (never enough on
+    /// their own to satisfy a threshold); extra weights are dropped.
+    pub fn new_weighted(
+        threshold: u32,
+        public_keys: Vec<CosmosPubKey>,
+        mut weights: Vec<u64>,
+    ) -> Self {
+        if weights.len() != public_keys.len() {
+            warn!(
+                "multisig weight count ({}) did not match public key count ({}); resizing to match",
+                weights.len(),
+                public_keys.len()
+            );
+            weights.resize(public_keys.len(), 0);
+        }
+
         Self {
             threshold,
             public_keys,
+            weights,
         }
     }
 }
@@ -88,11 +129,134 @@ impl CosmosAminoPubkey for MultisigThresholdPubKey {
             encoded.extend_from_slice(&pubkey_bytes);
         }
 
+        // A flat vote of 1 per signer is the original (pre-weighted)
+        // encoding, so it's left out entirely to keep existing unweighted
+        // multisig addresses byte-identical. Any real weight distribution
+        // has to commit to the address though - otherwise two keys with
+        // the same threshold/public_keys but different weights (e.g. one
+        // signer given enough weight to act alone) would be indistinguishable
+        // on-chain, since `threshold`/`public_keys` are public information.
+        if self.weights.iter().any(|&weight| weight != 1) {
+            for &weight in &self.weights {
+                encoded.push(WEIGHT_PREFIX);
+                let mut weight_bytes = vec![];
+                prost::encoding::encode_varint(weight, &mut weight_bytes);
+                encoded.extend_from_slice(weight_bytes.as_slice());
+            }
+        }
+
         trace!("pubkey bytes are: {:?}", encoded);
         encoded
     }
 }
 
+/// Maximum number of signers (and thus signatures) a single multisig level
+/// may declare. Without this, `verify_bytes` would attempt a verification
+/// for every signature against every signer with no ceiling on size.
+const MAX_MULTISIG_SIGNERS: usize = 64;
+
+/// Maximum recursion depth for a multisig whose own signers are themselves
+/// multisigs. Bounds the otherwise-unbounded recursion `verify_bytes` can be
+/// driven through by a maliciously nested key.
+const MAX_MULTISIG_DEPTH: u8 = 4;
+
+/// Maximum length, in bytes, of the sign-bytes message, checked before any
+/// crypto work is attempted.
+const MAX_SIGN_BYTES_LEN: usize = 1024 * 1024;
+
+/// Longest single signature accepted. secp256k1 and ed25519 signatures are
+/// far shorter. A nested multisig's own
+/// `MultiSignature` blob is itself treated as one `current_sig` by its
+/// parent, so this has to be large enough to hold a full-size nested
+/// multisig: `MAX_MULTISIG_SIGNERS` (64) ed25519 (tag + length + 64-byte
+/// signature) entries at 66 bytes each, plus bitarray
+/// overhead, comes to a little over 4KB - this is set comfortably clear of
+/// that so a legitimate max-size nested multisig inside a max-size outer
+/// multisig isn't spuriously rejected as "signature too long". A nested
+/// multisig whose own signers are large multisigs can produce a longer
+/// blob; that blob is rejected with `InvalidSignatureLen`.
+const MAX_SIGNATURE_LEN: usize = 8192;
+
+/// An ed25519 public key is always exactly 32 bytes.
+const ED25519_PUBKEY_LEN: usize = 32;
+
+/// Granular multisig parsing/verification failures. `decode_multisig_signature`
+/// and the `verify_bytes*` helpers all return this instead of collapsing
+/// every failure into `CryptoError::ParsingError` / `VerificationError`, so a
+/// `warn!`/`trace!` log can say exactly what went wrong. It's mapped to
+/// `CryptoError` only at the `VerifyingKey` trait boundary, so the public API
+/// is unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MultisigError {
+    /// Protobuf framing was malformed. rust-protobuf doesn't expose the byte
+    /// position of the failure, so this intentionally carries no offset
+    /// rather than reporting the blob length as a stand-in for one - every
+    /// malformed blob of the same size would otherwise log an identical,
+    /// meaningless "offset".
+    InvalidLen,
+    /// A single signature's length fell outside what any supported scheme
+    /// can produce.
+    InvalidSignatureLen { index: usize, len: usize },
+    /// A single public key's length fell outside what any supported scheme
+    /// can produce.
+    InvalidPubkeyLen { index: usize, len: usize },
+    /// The number of signatures didn't match the number of set bits in the
+    /// bitarray.
+    MismatchSignatureLen { found: usize, expected: usize },
+    /// A bitarray set bit named a signer slot past the end of `public_keys`.
+    InvalidBitIndex { index: usize, max: usize },
+    /// The same public key was declared more than once in a single
+    /// multisig level.
+    DuplicateSigner { index: usize },
+    /// The bitarray's `extra_bits_stored` field was outside `0..=8`, or it
+    /// was nonzero on an empty `elems` - either way, `CompactBitArray::len`
+    /// and `::get` can't be trusted to stay in bounds of `elems`.
+    InvalidBitArray { extra_bits_stored: u32 },
+    /// A signature did not verify: on the bit-indexed path, `index` is the
+    /// bit index of the signer whose signature failed; in the positional
+    /// fallback, `index` is the position of the signature that none of the
+    /// remaining signers verified.
+    SignatureVerificationFailed { index: usize },
+    /// A signature was checked against a signer that is itself a nested
+    /// multisig (`index` as for `SignatureVerificationFailed`: the signer's
+    /// bit index, or the signature's position in the positional fallback),
+    /// and that nested multisig's own verification
+    /// failed for `cause` - carried instead of collapsing to
+    /// `SignatureVerificationFailed`, so a failure inside a nested multisig
+    /// is still diagnosable.
+    Nested {
+        index: usize,
+        cause: Box<MultisigError>,
+    },
+    /// The summed weight of the signers who produced a valid signature
+    /// didn't reach the threshold.
+    InsufficientWeight { found: u64, required: u64 },
+    TooManySigners { found: usize, max: usize },
+    NestingTooDeep { depth: u8, max: u8 },
+    MessageTooLarge { len: usize, max: usize },
+}
+
+impl MultisigError {
+    fn into_crypto_error(self) -> CryptoError {
+        warn!("multisig verification failed: {:?}", self);
+        match self {
+            MultisigError::InvalidLen
+            | MultisigError::InvalidSignatureLen { .. }
+            | MultisigError::InvalidPubkeyLen { .. }
+            | MultisigError::InvalidBitArray { .. } => CryptoError::ParsingError,
+            MultisigError::MismatchSignatureLen { .. }
+            | MultisigError::InvalidBitIndex { .. }
+            | MultisigError::DuplicateSigner { .. }
+            | MultisigError::SignatureVerificationFailed { .. }
+            | MultisigError::Nested { .. }
+            | MultisigError::InsufficientWeight { .. }
+            | MultisigError::TooManySigners { .. }
+            | MultisigError::NestingTooDeep { .. }
+            | MultisigError::MessageTooLarge { .. } => CryptoError::VerificationError,
+        }
+    }
+}
+
 impl VerifyingKey for MultisigThresholdPubKey {
     fn verify_bytes(
         &self,
@@ -100,57 +264,207 @@ impl VerifyingKey for MultisigThresholdPubKey {
         sig: &[u8],
         sign_mode: SignMode,
     ) -> Result<(), CryptoError> {
+        self.verify_bytes_at_depth(bytes, sig, sign_mode, 0)
+            .map_err(MultisigError::into_crypto_error)
+    }
+}
+
+impl MultisigThresholdPubKey {
+    /// Same as `verify_bytes`, but carries the current nesting depth so a
+    /// multisig signer that is itself a multisig can be rejected once it
+    /// recurses past `MAX_MULTISIG_DEPTH`, instead of recursing unbounded.
+    fn verify_bytes_at_depth(
+        &self,
+        bytes: &[u8],
+        sig: &[u8],
+        sign_mode: SignMode,
+        depth: u8,
+    ) -> Result<(), MultisigError> {
+        if depth > MAX_MULTISIG_DEPTH {
+            return Err(MultisigError::NestingTooDeep {
+                depth,
+                max: MAX_MULTISIG_DEPTH,
+            });
+        }
+
+        if bytes.len() > MAX_SIGN_BYTES_LEN {
+            return Err(MultisigError::MessageTooLarge {
+                len: bytes.len(),
+                max: MAX_SIGN_BYTES_LEN,
+            });
+        }
+
+        if self.public_keys.len() > MAX_MULTISIG_SIGNERS {
+            return Err(MultisigError::TooManySigners {
+                found: self.public_keys.len(),
+                max: MAX_MULTISIG_SIGNERS,
+            });
+        }
+
+        if let Some(index) = first_duplicate_signer(&self.public_keys) {
+            return Err(MultisigError::DuplicateSigner { index });
+        }
+
         debug!("verifying multisig");
         trace!("Sign bytes are: {:?}", bytes);
-        let signatures = decode_multisig_signature(sig)?;
+        let (signatures, bitarray) = decode_multisig_signature(sig)?;
 
-        if signatures.len() < self.threshold as usize {
-            warn!(
-                "insufficient signatures in multisig signature. found: {}, expected at least: {}",
-                signatures.len(),
-                self.public_keys.len()
-            );
-            return Err(CryptoError::VerificationError);
+        if signatures.len() > MAX_MULTISIG_SIGNERS {
+            return Err(MultisigError::TooManySigners {
+                found: signatures.len(),
+                max: MAX_MULTISIG_SIGNERS,
+            });
+        }
+
+        match bitarray {
+            Some(bitarray) => {
+                self.verify_bytes_indexed(bytes, &signatures, &bitarray, sign_mode, depth)
+            }
+            None => self.verify_bytes_positional(bytes, &signatures, sign_mode, depth),
+        }
+    }
+    /// Verifies `signatures` against the signers selected by the set bits of
+    /// `bitarray`, in bit order: the k-th set bit names the signer
+    /// (`public_keys[bit_index]`) that must have produced the k-th signature.
+    /// This is O(n) instead of the O(n*m) try-every-key fallback, and it
+    /// can't accidentally attribute a signature to the wrong signer.
+    fn verify_bytes_indexed(
+        &self,
+        bytes: &[u8],
+        signatures: &[Vec<u8>],
+        bitarray: &CompactBitArray,
+        sign_mode: SignMode,
+        depth: u8,
+    ) -> Result<(), MultisigError> {
+        let set_bits: Vec<usize> = (0..bitarray.len()).filter(|&i| bitarray.get(i)).collect();
+
+        if set_bits.len() != signatures.len() {
+            return Err(MultisigError::MismatchSignatureLen {
+                found: signatures.len(),
+                expected: set_bits.len(),
+            });
+        }
+
+        let mut assignments = Vec::with_capacity(signatures.len());
+        for (index, (current_sig, bit_index)) in signatures.iter().zip(set_bits).enumerate() {
+            if current_sig.len() > MAX_SIGNATURE_LEN {
+                return Err(MultisigError::InvalidSignatureLen {
+                    index,
+                    len: current_sig.len(),
+                });
+            }
+
+            let signer = self.public_keys.get(bit_index).ok_or(MultisigError::InvalidBitIndex {
+                index: bit_index,
+                max: self.public_keys.len(),
+            })?;
+            assignments.push((bit_index, signer, current_sig));
         }
 
-        let mut verified_counter = 0;
+        verify_assignments(bytes, &assignments, sign_mode, depth)?;
+
+        // Weights are attacker-influenced (they come from the public key
+        // itself, which an attacker can construct with arbitrary values);
+        // `saturating_add` keeps a maliciously large weight from wrapping
+        // the running sum back down into "meets threshold" territory.
+        let verified_weight: u64 = assignments
+            .iter()
+            .map(|(bit_index, _, _)| self.weights[*bit_index])
+            .fold(0u64, |acc, weight| acc.saturating_add(weight));
+
+        if verified_weight < self.threshold as u64 {
+            Err(MultisigError::InsufficientWeight {
+                found: verified_weight,
+                required: self.threshold as u64,
+            })
+        } else {
+            debug!("Miltusig verified successfully");
+            Ok(())
+        }
+    }
+
+    /// Fallback used only when the signature carries no bitarray: tries every
+    /// remaining signer against every signature, same as before bit-indexed
+    /// verification was added.
+    fn verify_bytes_positional(
+        &self,
+        bytes: &[u8],
+        signatures: &[Vec<u8>],
+        sign_mode: SignMode,
+        depth: u8,
+    ) -> Result<(), MultisigError> {
+        let mut verified_weight: u64 = 0;
 
-        let mut signers: Vec<&CosmosPubKey> = self.public_keys.iter().collect();
-        for current_sig in &signatures {
+        let mut signers: Vec<(&CosmosPubKey, u64)> = self
+            .public_keys
+            .iter()
+            .zip(self.weights.iter().copied())
+            .collect();
+        for (index, current_sig) in signatures.iter().enumerate() {
             trace!("Checking sig: {:?}", current_sig);
             if current_sig.is_empty() {
                 trace!("skipping a signature because it was empty");
                 continue;
             }
+            if current_sig.len() > MAX_SIGNATURE_LEN {
+                return Err(MultisigError::InvalidSignatureLen {
+                    index,
+                    len: current_sig.len(),
+                });
+            }
 
             let mut signer_pos = None;
-            for (i, current_signer) in signers.iter().enumerate() {
+            // If a nested-multisig signer is tried and fails, keep its real
+            // cause around: if no other signer matches either, it's a much
+            // more useful diagnostic than a generic "verification failed".
+            let mut nested_failure = None;
+            for (i, (current_signer, _)) in signers.iter().enumerate() {
                 trace!("Checking pubkey: {:?}", current_signer);
-                // This technically support that one of the multisig signers is a multisig itself
-                let result = current_signer.verify_bytes(bytes, current_sig, sign_mode);
+                // One of the multisig signers can be a multisig itself; thread the
+                // nesting depth through so that recursion is bounded.
+                let verified = match current_signer {
+                    CosmosPubKey::Multisig(nested) => {
+                        match nested.verify_bytes_at_depth(bytes, current_sig, sign_mode, depth + 1)
+                        {
+                            Ok(()) => true,
+                            Err(cause) => {
+                                nested_failure = Some(cause);
+                                false
+                            }
+                        }
+                    }
+                    _ => current_signer
+                        .verify_bytes(bytes, current_sig, sign_mode)
+                        .is_ok(),
+                };
 
-                if result.is_ok() {
+                if verified {
                     signer_pos = Some(i);
-                    verified_counter += 1;
                     break;
                 }
             }
 
             // remove the signer that created this signature from the list to prevent a signer from signing multiple times
             if let Some(i) = signer_pos {
-                signers.remove(i);
+                let (_, weight) = signers.remove(i);
+                // Same overflow guard as `verify_bytes_indexed`: weights are
+                // attacker-influenced, so a plain `+=` could wrap.
+                verified_weight = verified_weight.saturating_add(weight);
+            } else if let Some(cause) = nested_failure {
+                return Err(MultisigError::Nested {
+                    index,
+                    cause: Box::new(cause),
+                });
             } else {
-                warn!(
-                    "signature was not generated by any of the signers: {:?}",
-                    current_sig
-                );
-                return Err(CryptoError::VerificationError);
+                return Err(MultisigError::SignatureVerificationFailed { index });
             }
         }
 
-        if verified_counter < self.threshold {
-            warn!("Not enough valid signatures have been provided");
-            Err(CryptoError::VerificationError)
+        if verified_weight < self.threshold as u64 {
+            Err(MultisigError::InsufficientWeight {
+                found: verified_weight,
+                required: self.threshold as u64,
+            })
         } else {
             debug!("Miltusig verified successfully");
             Ok(())
@@ -158,16 +472,195 @@ impl VerifyingKey for MultisigThresholdPubKey {
     }
 }
 
-fn decode_multisig_signature(raw_blob: &[u8]) -> Result<Vec<Vec<u8>>, CryptoError> {
+/// Mirrors cosmwasm-crypto's `BATCH_MAX_LEN`: the most ed25519 signatures
+/// verified together in a single batch-verification call.
+const ED25519_BATCH_MAX_LEN: usize = 256;
+
+/// Verifies each `(bit_index, signer, signature)` assignment against the
+/// shared sign bytes. Signers that are ed25519 keys are grouped and verified
+/// together via batch verification, `ED25519_BATCH_MAX_LEN` at a time, since
+/// every one of them is checked against the very same `bytes`; secp256k1 and
+/// nested-multisig signers fall back to individual verification, as does any
+/// ed25519 batch that fails (so the specific bad signer can still be
+/// attributed for logging).
+fn verify_assignments(
+    bytes: &[u8],
+    assignments: &[(usize, &CosmosPubKey, &Vec<u8>)],
+    sign_mode: SignMode,
+    depth: u8,
+) -> Result<(), MultisigError> {
+    let mut individually: Vec<&(usize, &CosmosPubKey, &Vec<u8>)> = vec![];
+    let mut ed25519_batch: Vec<&(usize, &CosmosPubKey, &Vec<u8>)> = vec![];
+
+    for assignment in assignments {
+        match ed25519_pubkey_bytes(assignment.1) {
+            Some(_) => ed25519_batch.push(assignment),
+            None => individually.push(assignment),
+        }
+    }
+
+    for chunk in ed25519_batch.chunks(ED25519_BATCH_MAX_LEN) {
+        if verify_ed25519_batch(bytes, chunk).is_err() {
+            // Fall back to individual verification so logs can pin down
+            // exactly which signer's signature was bad.
+            for assignment in chunk {
+                verify_one(bytes, assignment, sign_mode, depth)?;
+            }
+        }
+    }
+
+    for assignment in individually {
+        verify_one(bytes, assignment, sign_mode, depth)?;
+    }
+
+    Ok(())
+}
+
+fn verify_one(
+    bytes: &[u8],
+    (bit_index, signer, current_sig): &(usize, &CosmosPubKey, &Vec<u8>),
+    sign_mode: SignMode,
+    depth: u8,
+) -> Result<(), MultisigError> {
+    trace!("Checking pubkey at bit index {}: {:?}", bit_index, signer);
+    // One of the multisig signers can be a multisig itself; thread the
+    // nesting depth through so that recursion is bounded, and propagate its
+    // own MultisigError instead of collapsing it to a generic failure.
+    match signer {
+        CosmosPubKey::Multisig(nested) => nested
+            .verify_bytes_at_depth(bytes, current_sig, sign_mode, depth + 1)
+            .map_err(|cause| MultisigError::Nested {
+                index: *bit_index,
+                cause: Box::new(cause),
+            }),
+        _ => signer
+            .verify_bytes(bytes, current_sig, sign_mode)
+            .map_err(|_| MultisigError::SignatureVerificationFailed { index: *bit_index }),
+    }
+}
+
+fn verify_ed25519_batch(
+    bytes: &[u8],
+    chunk: &[&(usize, &CosmosPubKey, &Vec<u8>)],
+) -> Result<(), MultisigError> {
+    for (index, signer, _) in chunk {
+        let key = ed25519_pubkey_bytes(signer).expect("chunk was pre-filtered to ed25519 signers");
+        if key.len() != ED25519_PUBKEY_LEN {
+            return Err(MultisigError::InvalidPubkeyLen {
+                index: *index,
+                len: key.len(),
+            });
+        }
+    }
+
+    let messages: Vec<&[u8]> = vec![bytes; chunk.len()];
+    let signatures: Vec<&[u8]> = chunk.iter().map(|(_, _, sig)| sig.as_slice()).collect();
+    let public_keys: Vec<&[u8]> = chunk
+        .iter()
+        .map(|(_, signer, _)| {
+            ed25519_pubkey_bytes(signer).expect("chunk was pre-filtered to ed25519 signers")
+        })
+        .collect();
+
+    let first_index = chunk[0].0;
+    let batch_failed = || MultisigError::SignatureVerificationFailed { index: first_index };
+
+    let ok = enclave_crypto::ed25519_batch_verify(&messages, &signatures, &public_keys)
+        .map_err(|_| batch_failed())?;
+
+    if ok {
+        Ok(())
+    } else {
+        Err(batch_failed())
+    }
+}
+
+fn ed25519_pubkey_bytes(signer: &CosmosPubKey) -> Option<&[u8]> {
+    match signer {
+        CosmosPubKey::Ed25519(pk) => Some(pk.key.as_slice()),
+        _ => None,
+    }
+}
+
+/// Returns the index of the first public key that also appears earlier in
+/// `public_keys`, if any. A multisig that declares the same signer twice
+/// would let one signature count twice towards the threshold.
+fn first_duplicate_signer(public_keys: &[CosmosPubKey]) -> Option<usize> {
+    for (index, key) in public_keys.iter().enumerate() {
+        if public_keys[..index].contains(key) {
+            return Some(index);
+        }
+    }
+    None
+}
+
+/// A Cosmos `CompactBitArray`: `elems` packs one bit per declared signer,
+/// MSB-first, with only the high `extra_bits_stored` bits of the final byte
+/// significant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactBitArray {
+    extra_bits_stored: u32,
+    elems: Vec<u8>,
+}
+
+impl CompactBitArray {
+    /// Validates and converts a decoded protobuf `CompactBitArray`. Both
+    /// fields are attacker-controlled: an `extra_bits_stored` outside
+    /// `0..=8` (or nonzero with no `elems`) would make `len()` compute a bit
+    /// count past the end of `elems`, and `get()` would then index out of
+    /// bounds instead of failing gracefully.
+    fn from_proto(
+        ba: &cosmos_proto::crypto::multisig::multisig::CompactBitArray,
+    ) -> Result<Self, MultisigError> {
+        if ba.extra_bits_stored > 8 || (ba.extra_bits_stored != 0 && ba.elems.is_empty()) {
+            return Err(MultisigError::InvalidBitArray {
+                extra_bits_stored: ba.extra_bits_stored,
+            });
+        }
+
+        Ok(CompactBitArray {
+            extra_bits_stored: ba.extra_bits_stored,
+            elems: ba.elems.clone(),
+        })
+    }
+
+    fn len(&self) -> usize {
+        if self.elems.is_empty() {
+            return 0;
+        }
+        if self.extra_bits_stored == 0 {
+            self.elems.len() * 8
+        } else {
+            (self.elems.len() - 1) * 8 + self.extra_bits_stored as usize
+        }
+    }
+
+    fn get(&self, index: usize) -> bool {
+        let byte = self.elems[index / 8];
+        let bit_in_byte = 7 - (index % 8);
+        (byte & (1 << bit_in_byte)) != 0
+    }
+}
+
+fn decode_multisig_signature(
+    raw_blob: &[u8],
+) -> Result<(Vec<Vec<u8>>, Option<CompactBitArray>), MultisigError> {
     let ms = MultiSignature::parse_from_bytes(raw_blob).map_err(|err| {
-        warn!(
-            "Failed to decode the signature of a multisig key from protobuf bytes: {:?}",
+        trace!(
+            "Failed to decode the signature of a multisig key from protobuf bytes (blob was {} bytes): {:?}",
+            raw_blob.len(),
             err
         );
-        CryptoError::ParsingError
+        MultisigError::InvalidLen
     })?;
 
-    Ok(ms.signatures.into_vec())
+    let bitarray = ms
+        .bitarray
+        .as_ref()
+        .map(CompactBitArray::from_proto)
+        .transpose()?;
+
+    Ok((ms.signatures.into_vec(), bitarray))
 }
 
 // TODO delete this function after verifying multisig works right
@@ -230,6 +723,743 @@ fn decode_multisig_signature_old(raw_blob: &[u8]) -> Result<Vec<Vec<u8>>, Crypto
     Ok(signatures)
 }
 
+/// Fixtures shared by every `#[cfg(feature = "test")]` module below, so a
+/// deterministic ed25519 signer or an encoded `MultiSignature` blob isn't
+/// hand-rolled in five different places.
+#[cfg(feature = "test")]
+pub mod test_support {
+    use crate::types::CosmosPubKey;
+    use cosmos_proto::crypto::multisig::multisig::{CompactBitArray, MultiSignature};
+    use ed25519_dalek::{Keypair, PublicKey, SecretKey};
+    use protobuf::Message;
+
+    /// A deterministic ed25519 signer derived from a single-byte seed, so
+    /// tests don't need an RNG.
+    pub fn ed25519_signer(seed: u8) -> (CosmosPubKey, Keypair) {
+        let mut secret_bytes = [0u8; 32];
+        secret_bytes[0] = seed;
+        let secret = SecretKey::from_bytes(&secret_bytes).expect("32 bytes is a valid seed");
+        let public = PublicKey::from(&secret);
+        let keypair = Keypair { secret, public };
+        (
+            CosmosPubKey::Ed25519(cosmos_proto::crypto::ed25519::PubKey {
+                key: public.to_bytes().to_vec(),
+                ..Default::default()
+            }),
+            keypair,
+        )
+    }
+
+    /// Encodes a `MultiSignature` with no bitarray, for the legacy
+    /// positional (try-every-remaining-signer) verification path.
+    pub fn encode_signatures(signatures: Vec<Vec<u8>>) -> Vec<u8> {
+        let mut ms = MultiSignature::new();
+        ms.signatures = signatures.into();
+        ms.write_to_bytes().expect("encoding a MultiSignature never fails")
+    }
+
+    /// Encodes a `MultiSignature` with a bitarray whose set bits are exactly
+    /// `set_bits` out of `total_bits`, for the bit-indexed verification path.
+    pub fn encode_signatures_with_bitarray(
+        signatures: Vec<Vec<u8>>,
+        set_bits: &[usize],
+        total_bits: usize,
+    ) -> Vec<u8> {
+        let mut elems = vec![0u8; (total_bits + 7) / 8];
+        for &bit_index in set_bits {
+            elems[bit_index / 8] |= 1 << (7 - (bit_index % 8));
+        }
+
+        let mut bitarray = CompactBitArray::new();
+        bitarray.extra_bits_stored = (total_bits % 8) as u32;
+        bitarray.elems = elems;
+
+        let mut ms = MultiSignature::new();
+        ms.signatures = signatures.into();
+        ms.bitarray = protobuf::MessageField::some(bitarray);
+        ms.write_to_bytes().expect("encoding a MultiSignature never fails")
+    }
+
+    /// Encodes a `MultiSignature` whose bitarray has every one of the first
+    /// `num_keys` bits set - the common "every declared signer signed" case.
+    pub fn encode_all_signed(signatures: Vec<Vec<u8>>, num_keys: usize) -> Vec<u8> {
+        let set_bits: Vec<usize> = (0..num_keys).collect();
+        encode_signatures_with_bitarray(signatures, &set_bits, num_keys)
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod tests_amino_weight_encoding {
+    use super::test_support::ed25519_signer;
+    use super::{CosmosAminoPubkey, MultisigThresholdPubKey};
+
+    /// `new` (unweighted) and `new_weighted` with every weight set to 1 must
+    /// keep producing the exact same amino encoding - otherwise every
+    /// existing unweighted multisig account would change address the moment
+    /// weighted multisigs shipped.
+    pub fn test_legacy_all_weight_one_is_byte_identical_to_unweighted() {
+        let (signer_a, _) = ed25519_signer(1);
+        let (signer_b, _) = ed25519_signer(2);
+
+        let via_new = MultisigThresholdPubKey::new(2, vec![signer_a.clone(), signer_b.clone()]);
+        let via_weighted =
+            MultisigThresholdPubKey::new_weighted(2, vec![signer_a, signer_b], vec![1, 1]);
+
+        assert_eq!(
+            via_new.amino_bytes(),
+            via_weighted.amino_bytes(),
+            "an all-1 weight vector must not change the amino encoding"
+        );
+    }
+
+    /// Two keys with the same threshold and public keys but different weight
+    /// distributions must not encode identically - otherwise anyone who
+    /// knows the public `threshold`/`public_keys` of an existing weighted
+    /// multisig account could construct one with arbitrary weights (e.g.
+    /// giving a single signer enough weight to act alone) that resolves to
+    /// the exact same on-chain address.
+    pub fn test_different_weight_distributions_change_the_encoding() {
+        let (signer_a, _) = ed25519_signer(1);
+        let (signer_b, _) = ed25519_signer(2);
+
+        let low_weight_a = MultisigThresholdPubKey::new_weighted(
+            2,
+            vec![signer_a.clone(), signer_b.clone()],
+            vec![1, 1],
+        );
+        let high_weight_a =
+            MultisigThresholdPubKey::new_weighted(2, vec![signer_a, signer_b], vec![5, 1]);
+
+        assert_ne!(
+            low_weight_a.amino_bytes(),
+            high_weight_a.amino_bytes(),
+            "different weight distributions must commit to different addresses"
+        );
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod tests_verify_weighted_multisig {
+    use super::test_support::{ed25519_signer, encode_signatures, encode_signatures_with_bitarray};
+    use super::MultisigThresholdPubKey;
+    use cosmos_proto::tx::signing::SignMode;
+    use enclave_crypto::traits::VerifyingKey;
+    use ed25519_dalek::Signer;
+
+    /// A single high-weight signer can satisfy the threshold on its own, even
+    /// though it's only one of three declared keys.
+    pub fn test_weighted_threshold_met_by_single_high_weight_signer() {
+        let msg = b"weighted multisig sign bytes";
+
+        let (signer_a, keypair_a) = ed25519_signer(1);
+        let (signer_b, _keypair_b) = ed25519_signer(2);
+        let (signer_c, _keypair_c) = ed25519_signer(3);
+
+        // threshold 2, weights [2, 1, 1]: signer_a alone clears the bar.
+        let multisig = MultisigThresholdPubKey::new_weighted(
+            2,
+            vec![signer_a, signer_b, signer_c],
+            vec![2, 1, 1],
+        );
+
+        let sig_a = keypair_a.sign(msg).to_bytes().to_vec();
+
+        let result = multisig.verify_bytes(
+            msg,
+            &encode_signatures(vec![sig_a]),
+            SignMode::SIGN_MODE_LEGACY_AMINO_JSON,
+        );
+        assert!(result.is_ok(), "expected weighted threshold to be met");
+    }
+
+    /// Two valid signatures from low-weight signers can still fall short of
+    /// the threshold.
+    pub fn test_weighted_threshold_not_met_despite_two_valid_signatures() {
+        let msg = b"weighted multisig sign bytes";
+
+        let (signer_a, keypair_a) = ed25519_signer(1);
+        let (signer_b, keypair_b) = ed25519_signer(2);
+        let (signer_c, _keypair_c) = ed25519_signer(3);
+
+        // threshold 5, weights [1, 1, 1]: even two signers (weight 2) fall short.
+        let multisig = MultisigThresholdPubKey::new_weighted(
+            5,
+            vec![signer_a, signer_b, signer_c],
+            vec![1, 1, 1],
+        );
+
+        let sig_a = keypair_a.sign(msg).to_bytes().to_vec();
+        let sig_b = keypair_b.sign(msg).to_bytes().to_vec();
+
+        let result = multisig.verify_bytes(
+            msg,
+            &encode_signatures(vec![sig_a, sig_b]),
+            SignMode::SIGN_MODE_LEGACY_AMINO_JSON,
+        );
+        assert!(
+            result.is_err(),
+            "expected weighted threshold to not be met"
+        );
+    }
+
+    /// Summing weights with a plain wrapping `+` could let a huge weight
+    /// wrap the running total around (e.g. `1 + u64::MAX` wraps to `0`),
+    /// making an otherwise easily-satisfied threshold look unmet.
+    /// `saturating_add` must clamp instead of wrapping.
+    pub fn test_large_weight_sum_does_not_wrap_below_threshold() {
+        let msg = b"weighted multisig sign bytes";
+
+        let (signer_a, keypair_a) = ed25519_signer(1);
+        let (signer_b, keypair_b) = ed25519_signer(2);
+
+        let multisig = MultisigThresholdPubKey::new_weighted(
+            1,
+            vec![signer_a, signer_b],
+            vec![1, u64::MAX],
+        );
+
+        let sig_a = keypair_a.sign(msg).to_bytes().to_vec();
+        let sig_b = keypair_b.sign(msg).to_bytes().to_vec();
+
+        let result = multisig.verify_bytes(
+            msg,
+            &encode_signatures(vec![sig_a, sig_b]),
+            SignMode::SIGN_MODE_LEGACY_AMINO_JSON,
+        );
+        assert!(
+            result.is_ok(),
+            "a wrapping sum of 1 + u64::MAX would incorrectly reject this as unmet"
+        );
+    }
+
+    /// A `weights` vector shorter than `public_keys` must not panic -
+    /// `new_weighted` pads the missing slots with 0, which cannot satisfy
+    /// any positive threshold on its own, instead of leaving an under-length
+    /// vector that `verify_bytes_indexed` would later index out of bounds.
+    pub fn test_short_weights_vector_is_padded_not_panicking() {
+        let msg = b"weighted multisig sign bytes";
+
+        let (signer_a, _keypair_a) = ed25519_signer(1);
+        let (signer_b, keypair_b) = ed25519_signer(2);
+
+        // Only one weight provided for two public keys: signer_b's weight
+        // (index 1) is padded to 0.
+        let multisig =
+            MultisigThresholdPubKey::new_weighted(1, vec![signer_a, signer_b], vec![1]);
+
+        let sig_b = keypair_b.sign(msg).to_bytes().to_vec();
+
+        // signer_b alone, at its padded 0-weight slot, must not reach the
+        // threshold - and critically, this must not panic.
+        let result = multisig.verify_bytes(
+            msg,
+            &encode_signatures_with_bitarray(vec![sig_b], &[1], 2),
+            SignMode::SIGN_MODE_LEGACY_AMINO_JSON,
+        );
+        assert!(
+            result.is_err(),
+            "expected the padded 0-weight signer to not satisfy the threshold alone"
+        );
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod tests_verify_bitarray_multisig {
+    use super::test_support::{ed25519_signer, encode_signatures_with_bitarray};
+    use super::MultisigThresholdPubKey;
+    use cosmos_proto::crypto::multisig::multisig::{CompactBitArray, MultiSignature};
+    use cosmos_proto::tx::signing::SignMode;
+    use enclave_crypto::traits::VerifyingKey;
+    use ed25519_dalek::Signer;
+    use protobuf::Message;
+
+    /// A correct bitmap selects exactly the signers that produced the
+    /// signatures, in bit order, regardless of declaration order.
+    pub fn test_bitarray_selects_signers_by_index() {
+        let msg = b"bitarray multisig sign bytes";
+
+        let (signer_a, _keypair_a) = ed25519_signer(1);
+        let (signer_b, keypair_b) = ed25519_signer(2);
+        let (signer_c, keypair_c) = ed25519_signer(3);
+
+        let multisig =
+            MultisigThresholdPubKey::new(2, vec![signer_a, signer_b, signer_c]);
+
+        // Only signers at bit indices 1 and 2 signed.
+        let sig_b = keypair_b.sign(msg).to_bytes().to_vec();
+        let sig_c = keypair_c.sign(msg).to_bytes().to_vec();
+
+        let result = multisig.verify_bytes(
+            msg,
+            &encode_signatures_with_bitarray(vec![sig_b, sig_c], &[1, 2], 3),
+            SignMode::SIGN_MODE_LEGACY_AMINO_JSON,
+        );
+        assert!(result.is_ok(), "expected bit-indexed verification to pass");
+    }
+
+    /// The number of set bits must match the number of signatures.
+    pub fn test_bitarray_popcount_mismatch_is_rejected() {
+        let msg = b"bitarray multisig sign bytes";
+
+        let (signer_a, _keypair_a) = ed25519_signer(1);
+        let (signer_b, keypair_b) = ed25519_signer(2);
+        let (signer_c, _keypair_c) = ed25519_signer(3);
+
+        let multisig =
+            MultisigThresholdPubKey::new(1, vec![signer_a, signer_b, signer_c]);
+
+        let sig_b = keypair_b.sign(msg).to_bytes().to_vec();
+
+        // Bitarray claims two signers but only one signature is attached.
+        let result = multisig.verify_bytes(
+            msg,
+            &encode_signatures_with_bitarray(vec![sig_b], &[1, 2], 3),
+            SignMode::SIGN_MODE_LEGACY_AMINO_JSON,
+        );
+        assert!(
+            result.is_err(),
+            "expected popcount/signature-count mismatch to be rejected"
+        );
+    }
+
+    /// A set bit beyond the declared public keys must fail instead of
+    /// panicking.
+    pub fn test_bitarray_out_of_range_bit_is_rejected() {
+        let msg = b"bitarray multisig sign bytes";
+
+        let (signer_a, keypair_a) = ed25519_signer(1);
+        let (signer_b, _keypair_b) = ed25519_signer(2);
+
+        let multisig = MultisigThresholdPubKey::new(1, vec![signer_a, signer_b]);
+
+        let sig_a = keypair_a.sign(msg).to_bytes().to_vec();
+
+        // The bitarray has room for 8 signers, but this multisig only
+        // declares 2; bit index 5 doesn't correspond to any of them.
+        let result = multisig.verify_bytes(
+            msg,
+            &encode_signatures_with_bitarray(vec![sig_a], &[5], 8),
+            SignMode::SIGN_MODE_LEGACY_AMINO_JSON,
+        );
+        assert!(
+            result.is_err(),
+            "expected out-of-range bit index to be rejected"
+        );
+    }
+
+    /// An `extra_bits_stored` outside `0..=8` must be rejected before it can
+    /// drive `CompactBitArray::len`/`::get` out of bounds of `elems`.
+    pub fn test_bitarray_invalid_extra_bits_stored_is_rejected() {
+        let msg = b"bitarray multisig sign bytes";
+
+        let (signer_a, keypair_a) = ed25519_signer(1);
+        let multisig = MultisigThresholdPubKey::new(1, vec![signer_a]);
+
+        let sig_a = keypair_a.sign(msg).to_bytes().to_vec();
+
+        let mut bitarray = CompactBitArray::new();
+        bitarray.extra_bits_stored = 9; // only 0..=8 is meaningful for a single trailing byte
+        bitarray.elems = vec![0xffu8];
+
+        let mut ms = MultiSignature::new();
+        ms.signatures = vec![sig_a].into();
+        ms.bitarray = protobuf::MessageField::some(bitarray);
+        let sig = ms
+            .write_to_bytes()
+            .expect("encoding a MultiSignature never fails");
+
+        let result = multisig.verify_bytes(msg, &sig, SignMode::SIGN_MODE_LEGACY_AMINO_JSON);
+        assert!(
+            result.is_err(),
+            "expected an invalid extra_bits_stored to be rejected instead of panicking"
+        );
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod tests_verify_batched_ed25519_multisig {
+    use super::test_support::{ed25519_signer, encode_all_signed};
+    use super::MultisigThresholdPubKey;
+    use crate::types::CosmosPubKey;
+    use cosmos_proto::tx::signing::SignMode;
+    use enclave_crypto::traits::VerifyingKey;
+    use ed25519_dalek::{Keypair, Signer};
+
+    /// A 10-of-10 ed25519 multisig takes the batch-verification fast path
+    /// and still verifies correctly.
+    pub fn test_ten_of_ten_ed25519_multisig_verifies() {
+        let msg = b"batched ed25519 multisig sign bytes";
+
+        let signers: Vec<(CosmosPubKey, Keypair)> =
+            (0..10).map(|seed| ed25519_signer(seed as u8 + 1)).collect();
+        let public_keys: Vec<CosmosPubKey> =
+            signers.iter().map(|(pk, _)| pk.clone()).collect();
+        let signatures: Vec<Vec<u8>> = signers
+            .iter()
+            .map(|(_, kp)| kp.sign(msg).to_bytes().to_vec())
+            .collect();
+
+        let multisig = MultisigThresholdPubKey::new(10, public_keys);
+
+        let result = multisig.verify_bytes(
+            msg,
+            &encode_all_signed(signatures, 10),
+            SignMode::SIGN_MODE_LEGACY_AMINO_JSON,
+        );
+        assert!(result.is_ok(), "expected the full 10-of-10 batch to verify");
+    }
+
+    /// A single bad signature inside an otherwise valid batch must still be
+    /// rejected, not silently averaged away by the batch check.
+    pub fn test_single_bad_signature_in_batch_is_rejected() {
+        let msg = b"batched ed25519 multisig sign bytes";
+
+        let signers: Vec<(CosmosPubKey, Keypair)> =
+            (0..10).map(|seed| ed25519_signer(seed as u8 + 1)).collect();
+        let public_keys: Vec<CosmosPubKey> =
+            signers.iter().map(|(pk, _)| pk.clone()).collect();
+        let mut signatures: Vec<Vec<u8>> = signers
+            .iter()
+            .map(|(_, kp)| kp.sign(msg).to_bytes().to_vec())
+            .collect();
+
+        // Corrupt the last signature.
+        let last = signatures.last_mut().unwrap();
+        last[0] ^= 0xff;
+
+        let multisig = MultisigThresholdPubKey::new(10, public_keys);
+
+        let result = multisig.verify_bytes(
+            msg,
+            &encode_all_signed(signatures, 10),
+            SignMode::SIGN_MODE_LEGACY_AMINO_JSON,
+        );
+        assert!(
+            result.is_err(),
+            "expected a single bad signature to fail the whole batch"
+        );
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod benches_verify_batched_ed25519_multisig {
+    use super::test_support::{ed25519_signer, encode_all_signed};
+    use super::MultisigThresholdPubKey;
+    use crate::types::CosmosPubKey;
+    use cosmos_proto::tx::signing::SignMode;
+    use enclave_crypto::traits::VerifyingKey;
+    use ed25519_dalek::{Keypair, Signer};
+    use log::info;
+    use std::time::Instant;
+
+    /// There's no `#[bench]` harness wired up in this crate (same reason the
+    /// other modules here use plain `pub fn`s instead of `#[test]`), so this
+    /// times `ITERATIONS` verifications of a full 10-of-10 ed25519 multisig
+    /// and logs the per-iteration cost - enough to catch a regression that
+    /// silently falls back from the batch-verification fast path to
+    /// per-signature verification (e.g. a batch call that always errors and
+    /// is never noticed because the individual-verification fallback still
+    /// returns the right answer, just much slower).
+    pub fn bench_ten_of_ten_ed25519_multisig_verify() {
+        let msg = b"batched ed25519 multisig bench bytes";
+
+        let signers: Vec<(CosmosPubKey, Keypair)> =
+            (0..10).map(|seed| ed25519_signer(seed as u8 + 1)).collect();
+        let public_keys: Vec<CosmosPubKey> =
+            signers.iter().map(|(pk, _)| pk.clone()).collect();
+        let signatures: Vec<Vec<u8>> = signers
+            .iter()
+            .map(|(_, kp)| kp.sign(msg).to_bytes().to_vec())
+            .collect();
+
+        let multisig = MultisigThresholdPubKey::new(10, public_keys);
+        let sig = encode_all_signed(signatures, 10);
+
+        const ITERATIONS: u32 = 1000;
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            multisig
+                .verify_bytes(msg, &sig, SignMode::SIGN_MODE_LEGACY_AMINO_JSON)
+                .expect("bench signature must verify");
+        }
+        let elapsed = start.elapsed();
+        info!(
+            "10-of-10 ed25519 multisig verify: {:?} total over {} iterations ({:?}/iteration)",
+            elapsed,
+            ITERATIONS,
+            elapsed / ITERATIONS
+        );
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod tests_verify_dos_guards {
+    use super::test_support::{ed25519_signer, encode_all_signed, encode_signatures};
+    use super::{
+        MultisigThresholdPubKey, MAX_MULTISIG_DEPTH, MAX_MULTISIG_SIGNERS, MAX_SIGNATURE_LEN,
+        MAX_SIGN_BYTES_LEN,
+    };
+    use crate::types::CosmosPubKey;
+    use cosmos_proto::tx::signing::SignMode;
+    use enclave_crypto::traits::VerifyingKey;
+    use ed25519_dalek::{Keypair, Signer};
+
+    /// A multisig nested deeper than `MAX_MULTISIG_DEPTH` is rejected as soon
+    /// as recursion goes past that depth, without verifying the (otherwise
+    /// valid) innermost signature.
+    pub fn test_nested_multisig_beyond_depth_limit_is_rejected() {
+        let msg = b"nested multisig sign bytes";
+
+        let (mut current_signer, innermost_keypair) = ed25519_signer(1);
+        let mut current_sig = innermost_keypair.sign(msg).to_bytes().to_vec();
+
+        // Wrap a few levels past the depth limit; each level is a genuine
+        // 1-of-1 multisig around the previous one.
+        for _ in 0..(MAX_MULTISIG_DEPTH + 2) {
+            let wrapper = MultisigThresholdPubKey::new(1, vec![current_signer]);
+            current_sig = encode_signatures(vec![current_sig]);
+            current_signer = CosmosPubKey::Multisig(wrapper);
+        }
+
+        let outermost = match current_signer {
+            CosmosPubKey::Multisig(m) => m,
+            _ => unreachable!("just wrapped in CosmosPubKey::Multisig above"),
+        };
+
+        let result = outermost.verify_bytes(
+            msg,
+            &current_sig,
+            SignMode::SIGN_MODE_LEGACY_AMINO_JSON,
+        );
+        assert!(
+            result.is_err(),
+            "expected nesting beyond MAX_MULTISIG_DEPTH to be rejected"
+        );
+    }
+
+    /// A sign-bytes message longer than `MAX_SIGN_BYTES_LEN` is rejected
+    /// before any signature decoding or cryptographic work.
+    pub fn test_oversized_sign_bytes_rejected_early() {
+        let (signer, _keypair) = ed25519_signer(1);
+        let multisig = MultisigThresholdPubKey::new(1, vec![signer]);
+
+        let oversized_msg = vec![0u8; MAX_SIGN_BYTES_LEN + 1];
+
+        let result = multisig.verify_bytes(
+            &oversized_msg,
+            &[],
+            SignMode::SIGN_MODE_LEGACY_AMINO_JSON,
+        );
+        assert!(
+            result.is_err(),
+            "expected an oversized sign-bytes message to be rejected early"
+        );
+    }
+
+    /// A full-size nested multisig (`MAX_MULTISIG_SIGNERS` ed25519 signers,
+    /// all signing) must still fit within `MAX_SIGNATURE_LEN` as the outer
+    /// multisig's single `current_sig`, and verify successfully - the exact
+    /// boundary case `MAX_MULTISIG_SIGNERS` and `MAX_SIGNATURE_LEN` are
+    /// jointly supposed to allow.
+    pub fn test_max_size_nested_multisig_fits_signature_len_cap() {
+        let msg = b"full-size nested multisig sign bytes";
+
+        let signers: Vec<(CosmosPubKey, Keypair)> = (0..MAX_MULTISIG_SIGNERS)
+            .map(|seed| ed25519_signer((seed % 255) as u8 + 1))
+            .collect();
+        let public_keys: Vec<CosmosPubKey> =
+            signers.iter().map(|(pk, _)| pk.clone()).collect();
+        let signatures: Vec<Vec<u8>> = signers
+            .iter()
+            .map(|(_, kp)| kp.sign(msg).to_bytes().to_vec())
+            .collect();
+
+        let nested_sig = encode_all_signed(signatures, MAX_MULTISIG_SIGNERS);
+
+        assert!(
+            nested_sig.len() <= MAX_SIGNATURE_LEN,
+            "a full-size nested multisig blob ({} bytes) must fit within MAX_SIGNATURE_LEN ({})",
+            nested_sig.len(),
+            MAX_SIGNATURE_LEN
+        );
+
+        let nested = MultisigThresholdPubKey::new(MAX_MULTISIG_SIGNERS as u32, public_keys);
+        let outer = MultisigThresholdPubKey::new(1, vec![CosmosPubKey::Multisig(nested)]);
+        let outer_sig = encode_signatures(vec![nested_sig]);
+
+        let result = outer.verify_bytes(msg, &outer_sig, SignMode::SIGN_MODE_LEGACY_AMINO_JSON);
+        assert!(
+            result.is_ok(),
+            "expected a full-size nested multisig within an outer multisig to verify"
+        );
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod tests_multisig_error_variants {
+    use super::test_support::ed25519_signer;
+    use super::{MultisigError, MultisigThresholdPubKey};
+    use crate::types::CosmosPubKey;
+    use cosmos_proto::crypto::multisig::multisig::{CompactBitArray, MultiSignature};
+    use cosmos_proto::tx::signing::SignMode;
+    use ed25519_dalek::Signer;
+    use protobuf::Message;
+
+    /// Declaring the same signer twice in one multisig is reported as
+    /// `DuplicateSigner`, not a blanket verification error.
+    pub fn test_duplicate_signer_is_reported() {
+        let (signer, _keypair) = ed25519_signer(1);
+        let multisig = MultisigThresholdPubKey::new(1, vec![signer.clone(), signer]);
+
+        let result = multisig.verify_bytes_at_depth(
+            b"msg",
+            &[],
+            SignMode::SIGN_MODE_LEGACY_AMINO_JSON,
+            0,
+        );
+
+        assert_eq!(result, Err(MultisigError::DuplicateSigner { index: 1 }));
+    }
+
+    /// Falling short of the threshold is reported as `InsufficientWeight`
+    /// with the exact weight found and required, not a blanket error.
+    pub fn test_insufficient_weight_is_reported_with_amounts() {
+        let msg = b"weighted multisig sign bytes";
+        let (signer_a, keypair_a) = ed25519_signer(1);
+        let (signer_b, _keypair_b) = ed25519_signer(2);
+
+        let multisig =
+            MultisigThresholdPubKey::new_weighted(5, vec![signer_a, signer_b], vec![1, 1]);
+
+        let mut ms = MultiSignature::new();
+        ms.signatures = vec![keypair_a.sign(msg).to_bytes().to_vec()].into();
+        let sig = ms.write_to_bytes().expect("encoding a MultiSignature never fails");
+
+        let result =
+            multisig.verify_bytes_at_depth(msg, &sig, SignMode::SIGN_MODE_LEGACY_AMINO_JSON, 0);
+
+        assert_eq!(
+            result,
+            Err(MultisigError::InsufficientWeight {
+                found: 1,
+                required: 5
+            })
+        );
+    }
+
+    /// A bitarray popcount that disagrees with the number of signatures is
+    /// reported as `MismatchSignatureLen` with both counts, instead of a
+    /// blanket error.
+    pub fn test_bitarray_popcount_mismatch_is_reported_with_counts() {
+        let msg = b"bitarray multisig sign bytes";
+        let (signer_a, keypair_a) = ed25519_signer(1);
+        let (signer_b, _keypair_b) = ed25519_signer(2);
+
+        let multisig = MultisigThresholdPubKey::new(1, vec![signer_a, signer_b]);
+
+        let mut bitarray = CompactBitArray::new();
+        bitarray.extra_bits_stored = 2;
+        bitarray.elems = vec![0b1100_0000]; // both bits 0 and 1 set
+
+        let mut ms = MultiSignature::new();
+        ms.signatures = vec![keypair_a.sign(msg).to_bytes().to_vec()].into(); // only 1 signature
+        ms.bitarray = protobuf::MessageField::some(bitarray);
+        let sig = ms.write_to_bytes().expect("encoding a MultiSignature never fails");
+
+        let result =
+            multisig.verify_bytes_at_depth(msg, &sig, SignMode::SIGN_MODE_LEGACY_AMINO_JSON, 0);
+
+        assert_eq!(
+            result,
+            Err(MultisigError::MismatchSignatureLen {
+                found: 1,
+                expected: 2
+            })
+        );
+    }
+
+    /// A bitarray set bit past the end of `public_keys` is reported as
+    /// `InvalidBitIndex`, not reused as a `MismatchSignatureLen` - the two
+    /// failures mean different things and shouldn't be indistinguishable in
+    /// logs.
+    pub fn test_out_of_range_bit_index_is_reported() {
+        let msg = b"bitarray multisig sign bytes";
+        let (signer_a, keypair_a) = ed25519_signer(1);
+        let (signer_b, _keypair_b) = ed25519_signer(2);
+
+        let multisig = MultisigThresholdPubKey::new(1, vec![signer_a, signer_b]);
+
+        // The bitarray has room for 8 signers, but this multisig only
+        // declares 2; bit index 5 doesn't correspond to any of them.
+        let mut bitarray = CompactBitArray::new();
+        bitarray.extra_bits_stored = 0;
+        bitarray.elems = vec![0b0000_0100]; // bit index 5 set
+
+        let mut ms = MultiSignature::new();
+        ms.signatures = vec![keypair_a.sign(msg).to_bytes().to_vec()].into();
+        ms.bitarray = protobuf::MessageField::some(bitarray);
+        let sig = ms.write_to_bytes().expect("encoding a MultiSignature never fails");
+
+        let result =
+            multisig.verify_bytes_at_depth(msg, &sig, SignMode::SIGN_MODE_LEGACY_AMINO_JSON, 0);
+
+        assert_eq!(
+            result,
+            Err(MultisigError::InvalidBitIndex { index: 5, max: 2 })
+        );
+    }
+
+    /// When a signer is itself a nested multisig, the nested multisig's own
+    /// `MultisigError` is surfaced as `Nested { cause, .. }` instead of being
+    /// discarded in favor of a generic `SignatureVerificationFailed`.
+    pub fn test_nested_multisig_failure_reports_its_own_cause() {
+        let msg = b"outer multisig sign bytes";
+        let (inner_signer_a, keypair_a) = ed25519_signer(1);
+        let (inner_signer_b, _keypair_b) = ed25519_signer(2);
+
+        // The nested multisig requires weight 5, but only one weight-1
+        // signer actually signs: it fails with InsufficientWeight.
+        let nested = MultisigThresholdPubKey::new_weighted(
+            5,
+            vec![inner_signer_a, inner_signer_b],
+            vec![1, 1],
+        );
+
+        let mut inner_ms = MultiSignature::new();
+        inner_ms.signatures = vec![keypair_a.sign(msg).to_bytes().to_vec()].into();
+        let inner_sig = inner_ms
+            .write_to_bytes()
+            .expect("encoding a MultiSignature never fails");
+
+        let outer =
+            MultisigThresholdPubKey::new(1, vec![CosmosPubKey::Multisig(nested)]);
+
+        let mut outer_ms = MultiSignature::new();
+        outer_ms.signatures = vec![inner_sig].into();
+        let outer_sig = outer_ms
+            .write_to_bytes()
+            .expect("encoding a MultiSignature never fails");
+
+        let result = outer.verify_bytes_at_depth(
+            msg,
+            &outer_sig,
+            SignMode::SIGN_MODE_LEGACY_AMINO_JSON,
+            0,
+        );
+
+        assert_eq!(
+            result,
+            Err(MultisigError::Nested {
+                index: 0,
+                cause: Box::new(MultisigError::InsufficientWeight {
+                    found: 1,
+                    required: 5
+                }),
+            })
+        );
+    }
+}
+
 #[cfg(feature = "test")]
 pub mod tests_decode_multisig_signature {
     use super::decode_multisig_signature;
@@ -243,12 +1473,13 @@ pub mod tests_decode_multisig_signature {
 
         let sig = vec![10, 10, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 10, 4, 1, 2, 3, 4];
 
-        let result = decode_multisig_signature(sig.as_slice()).unwrap();
+        let (result, bitarray) = decode_multisig_signature(sig.as_slice()).unwrap();
         assert_eq!(
             result, expected,
             "Signature is: {:?} and result is: {:?}",
             sig, result
-        )
+        );
+        assert!(bitarray.is_none(), "legacy encoding carries no bitarray");
     }
 
     pub fn test_decode_long_leb128() {
@@ -277,12 +1508,13 @@ pub mod tests_decode_multisig_signature {
             0,
         ];
 
-        let result = decode_multisig_signature(sig.as_slice()).unwrap();
+        let (result, bitarray) = decode_multisig_signature(sig.as_slice()).unwrap();
         assert_eq!(
             result, expected,
             "Signature is: {:?} and result is: {:?}",
             sig, result
-        )
+        );
+        assert!(bitarray.is_none(), "legacy encoding carries no bitarray");
     }
 
     pub fn test_decode_wrong_long_leb128() {
@@ -320,12 +1552,13 @@ pub mod tests_decode_multisig_signature {
 
         let sig = vec![10, 0];
 
-        let result = decode_multisig_signature(sig.as_slice()).unwrap();
+        let (result, bitarray) = decode_multisig_signature(sig.as_slice()).unwrap();
         assert_eq!(
             result, expected,
             "Signature is: {:?} and result is: {:?}",
             sig, result
-        )
+        );
+        assert!(bitarray.is_none(), "legacy encoding carries no bitarray");
     }
 
     pub fn test_decode_malformed_sig_wrong_length() {